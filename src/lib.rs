@@ -14,6 +14,8 @@
 //  You should have received a copy of the GNU General Public License
 //  along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod zfs;
+
 use std::process::exit;
 
 pub fn wip() {
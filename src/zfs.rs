@@ -0,0 +1,233 @@
+//  Chobi: OpenZFS snapshot tool
+//  Copyright (C) 2025-2026  Ifaz Kabir
+
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, io, str::FromStr};
+
+/// ZFS_MAX_DATASET_NAME_LEN, which includes the trailing NUL.
+const MAX_NAME_LEN: usize = 256;
+
+/// A snapshot or bookmark guid, as reported by `zfs get -Hp guid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid(u64);
+
+impl Guid {
+    pub fn new(guid: u64) -> Self {
+        Self(guid)
+    }
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for Guid {
+    type Err = io::Error;
+
+    /// Parses the decimal form zfs prints with `-p`. Anything else means we
+    /// have misread the zfs output, so it is reported as invalid data.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // u64::from_str accepts a leading '+', which zfs never prints
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid guid {s:?} in zfs output"),
+            ));
+        }
+        s.parse().map(Self).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid guid {s:?} in zfs output: {e}"),
+            )
+        })
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn invalid_name(name: &str, reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid zfs name {name:?}: {reason}"),
+    )
+}
+
+fn check_length(name: &str) -> io::Result<()> {
+    if name.len() >= MAX_NAME_LEN {
+        return Err(invalid_name(
+            name,
+            &format!("longer than {} bytes", MAX_NAME_LEN - 1),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks a single path component, or the snapshot or bookmark name after the
+/// '@' or '#'. The component must be non-empty, use only the characters zfs
+/// allows, and must not be `.` or `..`. `name` is the full name being
+/// validated, and is what errors report.
+fn check_component(name: &str, component: &str) -> io::Result<()> {
+    if component.is_empty() {
+        return Err(invalid_name(name, "empty component"));
+    }
+    if component == "." || component == ".." {
+        return Err(invalid_name(name, "'.' and '..' are not valid components"));
+    }
+    if let Some(c) = component
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | ' ')))
+    {
+        return Err(invalid_name(name, &format!("invalid character {c:?}")));
+    }
+    Ok(())
+}
+
+/// Checks the dataset part of `name`, which is either `name` itself or the
+/// part before the '@'.
+fn check_dataset(name: &str, dataset: &str) -> io::Result<()> {
+    if dataset.contains('@') || dataset.contains('#') {
+        return Err(invalid_name(
+            name,
+            "dataset names cannot contain '@' or '#'",
+        ));
+    }
+    let mut components = dataset.split('/');
+    let pool = components.next().unwrap_or_default();
+    check_component(name, pool)?;
+    if !pool.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(invalid_name(name, "pool name must begin with a letter"));
+    }
+    for component in components {
+        check_component(name, component)?;
+    }
+    Ok(())
+}
+
+/// Validates a dataset name such as `tank/home/user`.
+pub fn validate_dataset_name(name: &str) -> io::Result<()> {
+    check_length(name)?;
+    check_dataset(name, name)
+}
+
+/// Validates the part of a snapshot name after the '@'. Use this for
+/// generated snapshot names before they are joined with a dataset. The
+/// joined name can still be too long, so check it with
+/// [`validate_snapshot_name`] once the dataset is known.
+pub fn validate_snapshot_component(snapshot: &str) -> io::Result<()> {
+    check_length(snapshot)?;
+    check_component(snapshot, snapshot)
+}
+
+/// Validates a full snapshot name such as `tank/home@daily`.
+pub fn validate_snapshot_name(name: &str) -> io::Result<()> {
+    check_length(name)?;
+    let Some((dataset, snapshot)) = name.split_once('@') else {
+        return Err(invalid_name(name, "missing '@'"));
+    };
+    check_dataset(name, dataset)?;
+    check_component(name, snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_names() {
+        assert!(validate_dataset_name("tank/home/user").is_ok());
+        assert!(validate_dataset_name("tank/my data/a.b:c-d_e").is_ok());
+        assert!(validate_snapshot_name("tank/home@autosnap_2025-01-03").is_ok());
+        assert!(validate_snapshot_component("autosnap_2025-01-03").is_ok());
+    }
+
+    #[test]
+    fn empty_components() {
+        assert!(validate_dataset_name("").is_err());
+        assert!(validate_dataset_name("tank//home").is_err());
+        assert!(validate_dataset_name("tank/home/").is_err());
+        assert!(validate_snapshot_name("tank@").is_err());
+        assert!(validate_snapshot_name("@daily").is_err());
+    }
+
+    #[test]
+    fn dot_components() {
+        assert!(validate_dataset_name("tank/.").is_err());
+        assert!(validate_dataset_name("tank/..").is_err());
+        assert!(validate_snapshot_name("tank@.").is_err());
+        assert!(validate_snapshot_name("tank@..").is_err());
+        assert!(validate_snapshot_component(".").is_err());
+        assert!(validate_snapshot_component("..").is_err());
+        // dots are fine as part of a longer component
+        assert!(validate_snapshot_name("tank/.hidden@..x").is_ok());
+    }
+
+    #[test]
+    fn separators() {
+        assert!(validate_snapshot_name("tank").is_err());
+        assert!(validate_snapshot_name("tank@a@b").is_err());
+        assert!(validate_dataset_name("tank@a").is_err());
+        assert!(validate_dataset_name("tank#a").is_err());
+        assert!(validate_dataset_name("tank/a*").is_err());
+    }
+
+    #[test]
+    fn pool_must_start_with_letter() {
+        assert!(validate_dataset_name("1tank").is_err());
+        assert!(validate_dataset_name("_tank/home").is_err());
+        assert!(validate_snapshot_name("1tank@daily").is_err());
+    }
+
+    #[test]
+    fn length_limit() {
+        let at_limit = format!("tank/{}", "a".repeat(250));
+        assert_eq!(at_limit.len(), 255);
+        assert!(validate_dataset_name(&at_limit).is_ok());
+        let over_limit = format!("{at_limit}a");
+        assert_eq!(over_limit.len(), 256);
+        assert!(validate_dataset_name(&over_limit).is_err());
+
+        let snapshot = format!("tank@{}", "a".repeat(250));
+        assert!(validate_snapshot_name(&snapshot).is_ok());
+        assert!(validate_snapshot_name(&format!("{snapshot}a")).is_err());
+
+        assert!(validate_snapshot_component(&"a".repeat(255)).is_ok());
+        assert!(validate_snapshot_component(&"a".repeat(256)).is_err());
+        assert!(validate_snapshot_component(&"a".repeat(400)).is_err());
+    }
+
+    #[test]
+    fn errors_report_full_name() {
+        let err = validate_snapshot_name("tank/.@x").unwrap_err();
+        assert!(err.to_string().contains("\"tank/.@x\""), "{err}");
+    }
+
+    #[test]
+    fn guid_rejects_malformed() {
+        for s in ["", "+1", "-1", " 1", "1 ", "0x1", "18446744073709551616"] {
+            assert!(s.parse::<Guid>().is_err(), "{s:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn guid_round_trips() {
+        let max = "18446744073709551615";
+        let guid: Guid = max.parse().unwrap();
+        assert_eq!(guid.get(), u64::MAX);
+        assert_eq!(guid.to_string(), max);
+    }
+}